@@ -0,0 +1,115 @@
+use embedded_hal::timer::{CountDown, Periodic};
+use void::Void;
+
+use crate::{RawTick, RollingTimer};
+
+/// Adapts any [`RollingTimer`] into an `embedded-hal` [`CountDown`]/[`Periodic`]
+/// timer, so it can be used with the large body of `embedded-hal` driver code
+/// that expects one.
+pub struct CountDownTimer<T: RollingTimer> {
+    timer: T,
+    start: T::Tick,
+    ticks: T::Tick,
+}
+
+impl<T: RollingTimer> CountDownTimer<T> {
+    /// Wrap `timer` in a `CountDown`/`Periodic` adapter. Until [`start`](CountDown::start)
+    /// is called, [`wait`](CountDown::wait) returns `Ok` immediately, rather
+    /// than blocking for an arbitrary, already-elapsed-at-construction-time
+    /// duration.
+    pub fn new(timer: T) -> Self {
+        Self {
+            start: timer.get_ticks(),
+            ticks: T::Tick::ZERO,
+            timer,
+        }
+    }
+}
+
+impl<T: RollingTimer> CountDown for CountDownTimer<T> {
+    type Time = T::Tick;
+
+    fn start<D>(&mut self, count: D)
+    where
+        D: Into<Self::Time>,
+    {
+        self.start = self.timer.get_ticks();
+        self.ticks = count.into();
+    }
+
+    fn wait(&mut self) -> nb::Result<(), Void> {
+        if self.timer.ticks_since(self.start) >= self.ticks {
+            // Re-arm for the next period, as `Periodic` promises.
+            self.start = self.timer.get_ticks();
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+impl<T: RollingTimer> Periodic for CountDownTimer<T> {}
+
+#[cfg(test)]
+mod test {
+    use core::cell::Cell;
+
+    use super::CountDownTimer;
+    use crate::RollingTimer;
+    use embedded_hal::timer::CountDown;
+
+    struct MockTimer(Cell<u32>);
+
+    impl MockTimer {
+        fn advance(&self, ticks: u32) {
+            self.0.set(self.0.get().wrapping_add(ticks));
+        }
+    }
+
+    impl RollingTimer for MockTimer {
+        type Tick = u32;
+        const TICKS_PER_SECOND: u32 = 1_000_000;
+
+        fn get_ticks(&self) -> u32 {
+            self.0.get()
+        }
+
+        fn is_initialized(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn wait_before_start_returns_immediately() {
+        let mut cd = CountDownTimer::new(MockTimer(Cell::new(1_000)));
+        assert_eq!(cd.wait(), Ok(()));
+    }
+
+    #[test]
+    fn wait_blocks_until_duration_elapses() {
+        let mut cd = CountDownTimer::new(MockTimer(Cell::new(0)));
+        cd.start(10u32);
+
+        assert_eq!(cd.wait(), Err(nb::Error::WouldBlock));
+
+        cd.timer.advance(9);
+        assert_eq!(cd.wait(), Err(nb::Error::WouldBlock));
+
+        cd.timer.advance(1);
+        assert_eq!(cd.wait(), Ok(()));
+    }
+
+    #[test]
+    fn wait_re_arms_for_the_next_period() {
+        let mut cd = CountDownTimer::new(MockTimer(Cell::new(0)));
+        cd.start(10u32);
+
+        cd.timer.advance(10);
+        assert_eq!(cd.wait(), Ok(()));
+
+        // Having just fired, it shouldn't fire again until another 10 ticks.
+        assert_eq!(cd.wait(), Err(nb::Error::WouldBlock));
+        cd.timer.advance(10);
+        assert_eq!(cd.wait(), Ok(()));
+    }
+}
@@ -0,0 +1,232 @@
+use core::ops::{Add, Sub};
+
+use crate::{RawTick, RollingTimer};
+
+/// A point in time, captured from a [`RollingTimer`].
+///
+/// Like the timer it was captured from, an `Instant` wraps: taking the
+/// difference between two instants that are half the counter's range
+/// (`1 << (bits - 1)` ticks) or more apart is ambiguous, since there's no way
+/// to tell whether the later instant is actually later, or just far enough
+/// in the past to have wrapped back around. Callers that may be comparing
+/// instants that far apart must treat that as an error case; in debug builds,
+/// [`duration_since`](Self::duration_since) asserts against it instead of
+/// silently returning a nonsense duration.
+pub struct Instant<T: RollingTimer> {
+    tick: T::Tick,
+}
+
+impl<T: RollingTimer> Instant<T> {
+    /// Capture the current tick of `timer`.
+    pub fn now(timer: &T) -> Self {
+        Self {
+            tick: timer.get_ticks(),
+        }
+    }
+
+    /// How much time has passed between `earlier` and `self`.
+    ///
+    /// `timer` is consulted for [`RollingTimer::ticks_per_second`], so the
+    /// resulting `Duration` converts to microseconds/milliseconds using the
+    /// rate actually in effect, even if it was only settled on at runtime.
+    pub fn duration_since(&self, earlier: Instant<T>, timer: &T) -> Duration<T> {
+        let ticks = self.tick.wrapping_sub(earlier.tick);
+        debug_assert!(
+            ticks.widen() < (1u64 << (T::Tick::BITS - 1)),
+            "duration_since: instants are too far apart to order unambiguously"
+        );
+        Duration {
+            ticks,
+            ticks_per_second: timer.ticks_per_second(),
+        }
+    }
+
+    /// How much time has passed between `self` and now.
+    pub fn elapsed(&self, timer: &T) -> Duration<T> {
+        Instant::now(timer).duration_since(Instant { tick: self.tick }, timer)
+    }
+}
+
+// Manual impls: `#[derive]` would otherwise require `T: Clone`/`T: Copy`,
+// even though only `T::Tick` is actually stored.
+impl<T: RollingTimer> Clone for Instant<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: RollingTimer> Copy for Instant<T> {}
+
+impl<T: RollingTimer> PartialEq for Instant<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tick == other.tick
+    }
+}
+
+impl<T: RollingTimer> core::fmt::Debug for Instant<T>
+where
+    T::Tick: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Instant").field("tick", &self.tick).finish()
+    }
+}
+
+impl<T: RollingTimer> Add<Duration<T>> for Instant<T> {
+    type Output = Instant<T>;
+
+    fn add(self, rhs: Duration<T>) -> Instant<T> {
+        Instant {
+            tick: self.tick.wrapping_add(rhs.ticks),
+        }
+    }
+}
+
+impl<T: RollingTimer> Sub<Duration<T>> for Instant<T> {
+    type Output = Instant<T>;
+
+    fn sub(self, rhs: Duration<T>) -> Instant<T> {
+        Instant {
+            tick: self.tick.wrapping_sub(rhs.ticks),
+        }
+    }
+}
+
+/// A span of time, measured in the ticks of some [`RollingTimer`].
+///
+/// Obtained from [`Instant::duration_since`]/[`Instant::elapsed`], which both
+/// capture the timer's [`ticks_per_second`](RollingTimer::ticks_per_second)
+/// at the time of the call, so `as_micros`/`as_millis` stay correct even for
+/// timers whose tick rate is only settled on at runtime.
+pub struct Duration<T: RollingTimer> {
+    ticks: T::Tick,
+    ticks_per_second: T::Tick,
+}
+
+impl<T: RollingTimer> Duration<T> {
+    /// This duration, in microseconds.
+    pub fn as_micros(&self) -> u64 {
+        (self.ticks.widen() * 1_000_000) / self.ticks_per_second.widen()
+    }
+
+    /// This duration, in milliseconds.
+    pub fn as_millis(&self) -> u64 {
+        (self.ticks.widen() * 1_000) / self.ticks_per_second.widen()
+    }
+}
+
+impl<T: RollingTimer> Clone for Duration<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: RollingTimer> Copy for Duration<T> {}
+
+impl<T: RollingTimer> PartialEq for Duration<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ticks == other.ticks
+    }
+}
+
+impl<T: RollingTimer> PartialOrd for Duration<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.ticks.widen().partial_cmp(&other.ticks.widen())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::cell::Cell;
+
+    use super::Instant;
+    use crate::RollingTimer;
+
+    struct MockTimer(Cell<u32>);
+
+    impl RollingTimer for MockTimer {
+        type Tick = u32;
+        const TICKS_PER_SECOND: u32 = 1_000_000;
+
+        fn get_ticks(&self) -> u32 {
+            self.0.get()
+        }
+
+        fn is_initialized(&self) -> bool {
+            true
+        }
+    }
+
+    /// A timer whose tick rate is only known at runtime, to exercise
+    /// [`RollingTimer::ticks_per_second`] overrides (e.g. a hardware timer
+    /// with a configurable prescaler).
+    struct RuntimeRateTimer(Cell<u32>);
+
+    impl RollingTimer for RuntimeRateTimer {
+        type Tick = u32;
+        const TICKS_PER_SECOND: u32 = 1_000_000;
+
+        fn ticks_per_second(&self) -> u32 {
+            self.0.get()
+        }
+
+        fn get_ticks(&self) -> u32 {
+            0
+        }
+
+        fn is_initialized(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn duration_since_handles_a_single_rollover() {
+        let timer = MockTimer(Cell::new(0));
+        let early = Instant::<MockTimer> { tick: u32::MAX - 4 };
+        let late = Instant::<MockTimer> { tick: 5 };
+
+        assert_eq!(late.duration_since(early, &timer).as_micros(), 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn duration_since_rejects_ambiguous_deltas() {
+        let timer = MockTimer(Cell::new(0));
+        let early = Instant::<MockTimer> { tick: 0 };
+        let late = Instant::<MockTimer> { tick: 1 << 31 };
+
+        // Exactly half the range apart: which one is "earlier" is undefined.
+        let _ = late.duration_since(early, &timer);
+    }
+
+    #[test]
+    fn duration_since_uses_the_timer_s_runtime_tick_rate() {
+        let timer = RuntimeRateTimer(Cell::new(500_000));
+        let early = Instant::<RuntimeRateTimer> { tick: 0 };
+        let late = Instant::<RuntimeRateTimer> { tick: 500_000 };
+
+        // At the configured 500kHz rate, 500,000 ticks is one second.
+        assert_eq!(late.duration_since(early, &timer).as_millis(), 1_000);
+    }
+
+    #[test]
+    fn elapsed_reflects_ticks_advanced_on_the_timer() {
+        let timer = MockTimer(Cell::new(0));
+        let start = Instant::now(&timer);
+
+        timer.0.set(1_000);
+        assert_eq!(start.elapsed(&timer).as_micros(), 1_000);
+    }
+
+    #[test]
+    fn add_and_sub_duration_wrap_like_the_underlying_timer() {
+        let timer = MockTimer(Cell::new(u32::MAX - 4));
+        let start = Instant::now(&timer);
+
+        timer.0.set(5);
+        let duration = Instant::now(&timer).duration_since(start, &timer);
+
+        let back_to_start = (start + duration) - duration;
+        assert_eq!(back_to_start, start);
+    }
+}
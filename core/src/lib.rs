@@ -0,0 +1,189 @@
+//! Groundhog is a small, portable API for rolling/wrapping hardware and
+//! software tick counters.
+//!
+//! A [`RollingTimer`] never stops and never resets: it counts up from zero,
+//! wraps back around to zero once it overflows `Self::Tick`, and keeps
+//! going. Callers are expected to take a tick, do some work, and then ask
+//! the timer how many ticks have passed since, using [`RollingTimer::ticks_since`]
+//! (or one of its `micros`/`millis` siblings) rather than subtracting raw
+//! tick values themselves, so a single rollover is handled transparently.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod count_down;
+mod instant;
+#[cfg(feature = "std")]
+mod std_timer;
+
+pub use count_down::CountDownTimer;
+pub use instant::{Duration, Instant};
+#[cfg(feature = "std")]
+pub use std_timer::Timer;
+
+/// A primitive integer type that can represent a single tick of a
+/// [`RollingTimer`].
+///
+/// This only exists so that [`RollingTimer`]'s default methods can perform
+/// wrapping arithmetic without requiring every implementor to hand-roll
+/// `ticks_since`/`micros_since`/`millis_since` themselves. It is implemented
+/// for `u32` and `u64`.
+pub trait RawTick: Copy + PartialOrd {
+    /// The width of this tick type, in bits. `u32::BITS`/`u64::BITS`.
+    const BITS: u32;
+
+    /// The zero value of this tick type.
+    const ZERO: Self;
+
+    /// Widen this tick value to a `u64`, for use in intermediate math.
+    fn widen(self) -> u64;
+
+    /// Narrow a `u64` back down to this tick type, truncating if necessary.
+    fn narrow(wide: u64) -> Self;
+
+    /// Subtract `other` from `self`, wrapping on overflow.
+    fn wrapping_sub(self, other: Self) -> Self;
+
+    /// Add `other` to `self`, wrapping on overflow.
+    fn wrapping_add(self, other: Self) -> Self;
+}
+
+impl RawTick for u32 {
+    const BITS: u32 = u32::BITS;
+    const ZERO: Self = 0;
+
+    fn widen(self) -> u64 {
+        self as u64
+    }
+
+    fn narrow(wide: u64) -> Self {
+        wide as u32
+    }
+
+    fn wrapping_sub(self, other: Self) -> Self {
+        u32::wrapping_sub(self, other)
+    }
+
+    fn wrapping_add(self, other: Self) -> Self {
+        u32::wrapping_add(self, other)
+    }
+}
+
+impl RawTick for u64 {
+    const BITS: u32 = u64::BITS;
+    const ZERO: Self = 0;
+
+    fn widen(self) -> u64 {
+        self
+    }
+
+    fn narrow(wide: u64) -> Self {
+        wide
+    }
+
+    fn wrapping_sub(self, other: Self) -> Self {
+        u64::wrapping_sub(self, other)
+    }
+
+    fn wrapping_add(self, other: Self) -> Self {
+        u64::wrapping_add(self, other)
+    }
+}
+
+/// A monotonic, wrapping tick counter, backed by hardware or software.
+pub trait RollingTimer {
+    /// The integer type used to represent a single tick of this timer.
+    type Tick: RawTick;
+
+    /// The number of ticks that occur in one second, for implementations
+    /// whose tick rate is fixed at compile time.
+    const TICKS_PER_SECOND: Self::Tick;
+
+    /// Obtain the current value of the counter, in ticks.
+    fn get_ticks(&self) -> Self::Tick;
+
+    /// The number of ticks that occur in one second, for *this* timer
+    /// instance.
+    ///
+    /// The default implementation just returns [`TICKS_PER_SECOND`](Self::TICKS_PER_SECOND),
+    /// which is all that's needed when the tick rate is fixed at compile
+    /// time. Implementations whose tick rate can be changed at runtime (for
+    /// example, by selecting a hardware prescaler during initialization)
+    /// should override this to report the effective rate instead, since
+    /// `micros_since`/`millis_since` are defined in terms of it.
+    fn ticks_per_second(&self) -> Self::Tick {
+        Self::TICKS_PER_SECOND
+    }
+
+    /// Has this timer been initialized yet? Before it has, `get_ticks` is
+    /// not meaningful and will typically just return zero.
+    fn is_initialized(&self) -> bool;
+
+    /// Obtain the current value of the counter, widened to 64 bits.
+    ///
+    /// The default implementation just widens [`get_ticks`](Self::get_ticks),
+    /// so its value still wraps back around to zero whenever `Self::Tick`
+    /// does. Implementations that maintain their own wider counter
+    /// (for example, a software-incremented high word) should override this
+    /// to remain valid across any number of rollovers.
+    fn get_ticks_64(&self) -> u64 {
+        self.get_ticks().widen()
+    }
+
+    /// Determine how many ticks have elapsed since `old_tick`, correctly
+    /// handling a single rollover of the counter.
+    fn ticks_since(&self, old_tick: Self::Tick) -> Self::Tick {
+        self.get_ticks().wrapping_sub(old_tick)
+    }
+
+    /// Like [`ticks_since`](Self::ticks_since), expressed in microseconds.
+    fn micros_since(&self, old_tick: Self::Tick) -> Self::Tick {
+        let ticks = self.ticks_since(old_tick).widen();
+        let tps = self.ticks_per_second().widen();
+        Self::Tick::narrow((ticks * 1_000_000) / tps)
+    }
+
+    /// Like [`ticks_since`](Self::ticks_since), expressed in milliseconds.
+    fn millis_since(&self, old_tick: Self::Tick) -> Self::Tick {
+        let ticks = self.ticks_since(old_tick).widen();
+        let tps = self.ticks_per_second().widen();
+        Self::Tick::narrow((ticks * 1_000) / tps)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RawTick;
+
+    #[test]
+    fn u32_wrapping_sub_handles_rollover() {
+        assert_eq!(RawTick::wrapping_sub(5u32, 3u32), 2);
+        assert_eq!(RawTick::wrapping_sub(1u32, 2u32), u32::MAX);
+        assert_eq!(RawTick::wrapping_sub(0u32, u32::MAX), 1);
+    }
+
+    #[test]
+    fn u64_wrapping_sub_handles_rollover() {
+        assert_eq!(RawTick::wrapping_sub(5u64, 3u64), 2);
+        assert_eq!(RawTick::wrapping_sub(1u64, 2u64), u64::MAX);
+        assert_eq!(RawTick::wrapping_sub(0u64, u64::MAX), 1);
+    }
+
+    #[test]
+    fn wrapping_add_handles_overflow() {
+        assert_eq!(RawTick::wrapping_add(u32::MAX, 1u32), 0);
+        assert_eq!(RawTick::wrapping_add(u64::MAX, 1u64), 0);
+    }
+
+    #[test]
+    fn widen_and_narrow_round_trip() {
+        assert_eq!(u32::narrow(RawTick::widen(1234u32)), 1234);
+        assert_eq!(u64::narrow(RawTick::widen(1234u64)), 1234);
+        // Narrowing truncates rather than panicking.
+        assert_eq!(u32::narrow(0x1_0000_0001), 1);
+    }
+
+    #[test]
+    fn zero_is_the_identity_for_wrapping_sub() {
+        assert_eq!(RawTick::wrapping_sub(42u32, u32::ZERO), 42);
+        assert_eq!(RawTick::wrapping_sub(42u64, u64::ZERO), 42);
+    }
+}
@@ -5,6 +5,10 @@
 //! as a monotonic counter will not work when the debugger is not attached, which
 //! in turn will make scheduling operations not work as expected.
 //!
+//! `GlobalRollingTimer` also implements RTIC's `Monotonic` compare interrupt
+//! (`set_compare`/`clear_compare_flag`), so scheduled tasks wake the core at
+//! the target instant instead of requiring something to busy-poll `now()`.
+//!
 //! # Usage
 //!
 //! To use the the `GlobalRollingTimer` with RTIC, it first needs to be selected
@@ -44,18 +48,82 @@ use groundhog::RollingTimer;
 use nrf52840_hal::{pac::timer0::RegisterBlock as RegBlock0, timer::Instance};
 use rtic::Monotonic;
 
-use core::sync::atomic::{AtomicPtr, Ordering};
+use core::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
 
 static TIMER_PTR: AtomicPtr<RegBlock0> = AtomicPtr::new(core::ptr::null_mut());
 
+/// The software-maintained high word of the 64-bit tick count, incremented
+/// each time the hardware counter rolls over. See [`tick_isr`].
+static HIGH_WORD: AtomicU32 = AtomicU32::new(0);
+
+/// Pack a 64-bit tick count back together out of [`HIGH_WORD`] and the
+/// hardware's 32-bit counter. Split out of [`RollingTimer::get_ticks_64`] so
+/// the packing itself can be unit tested without any hardware.
+fn combine_ticks(high: u32, low: u32) -> u64 {
+    ((high as u64) << 32) | (low as u64)
+}
+
+/// CC channel used to capture the current count for [`RollingTimer::get_ticks`].
+const CC_TICKS: usize = 0;
+
+/// CC channel used to detect the counter rolling over, so [`HIGH_WORD`] can be
+/// incremented. Programmed to fire at the last tick before wraparound.
+const CC_ROLLOVER: usize = 1;
+
+/// CC channel used by the RTIC `Monotonic` impl to schedule a wake-up compare
+/// interrupt. See [`Monotonic::set_compare`]/[`Monotonic::clear_compare_flag`].
+///
+/// Deliberately *not* channel 0: `Instance::set_periodic`/`timer_start` wire
+/// CC[0] to `SHORTS.COMPARE0_CLEAR`, an automatic `TASKS_CLEAR` on compare
+/// match. If scheduling reused that channel, every `set_compare` call would
+/// arm an auto-clear at an arbitrary future instant, and reaching it would
+/// reset the whole free-running counter out from under [`RollingTimer::get_ticks_64`].
+/// `init_with_config` below avoids that helper entirely and drives the
+/// registers directly, with `shorts` left at its reset value (nothing
+/// auto-clears), so this channel can hold an arbitrary compare value safely.
+const CC_SCHEDULE: usize = 2;
+
+/// The effective tick rate of whichever timer [`GlobalRollingTimer::init`] or
+/// [`GlobalRollingTimer::init_with_config`] was last called with. Defaults to
+/// the 1MHz rate that plain `init` hardcodes.
+static RUNTIME_TICKS_PER_SECOND: AtomicU32 = AtomicU32::new(1_000_000);
+
+/// Selects the width of the nrf52 TIMER's hardware counter (its `BITMODE`).
+///
+/// A narrower counter rolls over sooner, trading off against resolution and
+/// how often software needs to deal with wraparound.
+#[derive(Debug, Clone, Copy)]
+pub enum Bitmode {
+    B08,
+    B16,
+    B24,
+    B32,
+}
+
+impl Bitmode {
+    fn max_value(self) -> u32 {
+        match self {
+            Bitmode::B08 => 0x0000_00FF,
+            Bitmode::B16 => 0x0000_FFFF,
+            Bitmode::B24 => 0x00FF_FFFF,
+            Bitmode::B32 => 0xFFFF_FFFF,
+        }
+    }
+}
+
 /// A global rolling timer
 ///
 /// This must be initialized with a timer (like `TIMER0`) once,
 /// on startup, before valid timer values will be returned. Until then,
 /// a timer value of 0 ticks will always be returned.
 ///
-/// At the moment, this is limited to a 32-bit 1MHz timer, which has a
-/// maximum observable time delta of 71m34s.
+/// By default (via [`init`](Self::init)) this is a 32-bit 1MHz timer, which
+/// has a maximum observable time delta of 71m34s via
+/// [`RollingTimer::get_ticks`]/[`RollingTimer::ticks_since`]. For deltas that
+/// may span longer than that, use [`RollingTimer::get_ticks_64`] instead,
+/// which is kept glitch-free across any number of rollovers by [`tick_isr`].
+/// Use [`init_with_config`](Self::init_with_config) to trade resolution for a
+/// longer rollover window, or vice versa.
 pub struct GlobalRollingTimer;
 
 impl GlobalRollingTimer {
@@ -64,16 +132,92 @@ impl GlobalRollingTimer {
     }
 
     pub fn init<T: Instance>(timer: T) {
-        timer.set_periodic();
-        timer.timer_start(0xFFFF_FFFFu32);
+        Self::init_with_config(
+            timer,
+            TimerConfig {
+                prescaler: 4,
+                bitmode: Bitmode::B32,
+            },
+        );
+    }
+
+    /// Like [`init`](Self::init), but lets the caller pick the nrf52 TIMER's
+    /// `PRESCALER` (0..=9, giving `16MHz >> prescaler`) and `BITMODE`,
+    /// instead of hardcoding a 32-bit 1MHz timer.
+    ///
+    /// `ticks_since`/`micros_since`/`millis_since` (and the `DelayUs`/`DelayMs`
+    /// impls built on top of them) all scale to match the configured rate.
+    pub fn init_with_config<T: Instance>(timer: T, config: TimerConfig) {
+        assert!(config.prescaler <= 9, "PRESCALER must be in 0..=9");
+
         let t0 = timer.as_timer0();
 
+        // Deliberately not `Instance::set_periodic`/`timer_start`: those wire
+        // CC[0]'s `COMPARE0_CLEAR` shortcut to auto-clear the counter on
+        // compare match, which would collide with `CC_SCHEDULE` once RTIC
+        // starts reprogramming it. A rolling counter just needs to free-run
+        // and wrap at its own `BITMODE` width, so the registers are driven
+        // directly here instead, with `shorts` left disabled.
+        t0.shorts.write(|w| w);
+        t0.prescaler
+            .write(|w| unsafe { w.prescaler().bits(config.prescaler) });
+        t0.bitmode.write(|w| match config.bitmode {
+            Bitmode::B08 => w._08bit(),
+            Bitmode::B16 => w._16bit(),
+            Bitmode::B24 => w._24bit(),
+            Bitmode::B32 => w._32bit(),
+        });
+        t0.tasks_clear.write(|w| unsafe { w.bits(1) });
+        t0.tasks_start.write(|w| unsafe { w.bits(1) });
+
+        let max = config.bitmode.max_value();
+        t0.cc[CC_ROLLOVER].write(|w| unsafe { w.bits(max) });
+        t0.intenset.write(|w| w.compare1().set_bit());
+
+        RUNTIME_TICKS_PER_SECOND.store(prescaler_ticks_per_second(config.prescaler), Ordering::SeqCst);
+
         let old_ptr = TIMER_PTR.swap(t0 as *const _ as *mut _, Ordering::SeqCst);
 
         debug_assert!(old_ptr == core::ptr::null_mut());
     }
 }
 
+/// The effective tick rate produced by a given `PRESCALER` setting:
+/// `16MHz >> prescaler`. Split out of [`GlobalRollingTimer::init_with_config`]
+/// so the frequency math can be unit tested without any hardware.
+fn prescaler_ticks_per_second(prescaler: u8) -> u32 {
+    16_000_000u32 >> prescaler
+}
+
+/// Configuration accepted by [`GlobalRollingTimer::init_with_config`].
+#[derive(Debug, Clone, Copy)]
+pub struct TimerConfig {
+    /// Clock prescaler, giving an effective tick rate of `16MHz >> prescaler`.
+    /// Must be in `0..=9`.
+    pub prescaler: u8,
+    /// Width of the hardware counter.
+    pub bitmode: Bitmode,
+}
+
+/// Services the rollover compare event (`CC_ROLLOVER`): if it has fired,
+/// clears it and increments [`HIGH_WORD`], the software high word backing
+/// [`RollingTimer::get_ticks_64`].
+///
+/// A nrf52 TIMER only has a single shared IRQ vector covering all of its
+/// compare channels, so this can't be given its own interrupt to own. Under
+/// RTIC (see the module docs), that vector is RTIC's, and `clear_compare_flag`
+/// below calls this on every entry so `CC_ROLLOVER` still gets serviced
+/// alongside `CC_SCHEDULE`. If you are driving `GlobalRollingTimer` without
+/// RTIC, call this yourself from `TIMERn`'s interrupt handler.
+pub fn tick_isr() {
+    if let Some(t0) = unsafe { TIMER_PTR.load(Ordering::SeqCst).as_ref() } {
+        if t0.events_compare[CC_ROLLOVER].read().bits() != 0 {
+            t0.events_compare[CC_ROLLOVER].write(|w| unsafe { w.bits(0) });
+            HIGH_WORD.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
 impl Monotonic for GlobalRollingTimer {
     type Instant = i32;
     type Duration = i32;
@@ -92,12 +236,20 @@ impl Monotonic for GlobalRollingTimer {
         }
     }
 
-    fn set_compare(&mut self, _instant: Self::Instant) {
-        todo!()
+    fn set_compare(&mut self, instant: Self::Instant) {
+        if let Some(t0) = unsafe { TIMER_PTR.load(Ordering::SeqCst).as_ref() } {
+            t0.cc[CC_SCHEDULE].write(|w| unsafe { w.bits(instant as u32) });
+            t0.intenset.write(|w| w.compare2().set_bit());
+        }
     }
 
     fn clear_compare_flag(&mut self) {
-        todo!()
+        if let Some(t0) = unsafe { TIMER_PTR.load(Ordering::SeqCst).as_ref() } {
+            t0.events_compare[CC_SCHEDULE].write(|w| unsafe { w.bits(0) });
+        }
+        // This ISR entry is also the only chance `CC_ROLLOVER` gets to be
+        // serviced when RTIC owns the vector; see `tick_isr`.
+        tick_isr();
     }
 }
 
@@ -105,15 +257,34 @@ impl RollingTimer for GlobalRollingTimer {
     type Tick = u32;
     const TICKS_PER_SECOND: u32 = 1_000_000;
 
+    fn ticks_per_second(&self) -> u32 {
+        RUNTIME_TICKS_PER_SECOND.load(Ordering::SeqCst)
+    }
+
     fn get_ticks(&self) -> u32 {
         if let Some(t0) = unsafe { TIMER_PTR.load(Ordering::SeqCst).as_ref() } {
-            t0.tasks_capture[1].write(|w| unsafe { w.bits(1) });
-            t0.cc[1].read().bits()
+            t0.tasks_capture[CC_TICKS].write(|w| unsafe { w.bits(1) });
+            t0.cc[CC_TICKS].read().bits()
         } else {
             0
         }
     }
 
+    fn get_ticks_64(&self) -> u64 {
+        // Lock-free double read: sample the high word, capture the low word,
+        // then re-sample the high word. If it changed, the low word we
+        // captured may straddle the rollover that bumped it, so retry.
+        loop {
+            let high_before = HIGH_WORD.load(Ordering::SeqCst);
+            let low = self.get_ticks();
+            let high_after = HIGH_WORD.load(Ordering::SeqCst);
+
+            if high_before == high_after {
+                return combine_ticks(high_after, low);
+            }
+        }
+    }
+
     fn is_initialized(&self) -> bool {
         TIMER_PTR.load(Ordering::SeqCst) != core::ptr::null_mut()
     }
@@ -122,7 +293,7 @@ impl RollingTimer for GlobalRollingTimer {
 impl DelayUs<u32> for GlobalRollingTimer {
     fn delay_us(&mut self, us: u32) {
         let start = self.get_ticks();
-        while self.ticks_since(start) < us {}
+        while self.micros_since(start) < us {}
     }
 }
 
@@ -133,3 +304,38 @@ impl DelayMs<u32> for GlobalRollingTimer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{combine_ticks, prescaler_ticks_per_second, Bitmode};
+
+    #[test]
+    fn combine_ticks_packs_high_and_low_words() {
+        assert_eq!(combine_ticks(0, 0), 0);
+        assert_eq!(combine_ticks(0, 0xFFFF_FFFF), 0x0000_0000_FFFF_FFFF);
+        assert_eq!(combine_ticks(1, 0), 0x0000_0001_0000_0000);
+        assert_eq!(combine_ticks(1, 5), 0x0000_0001_0000_0005);
+        assert_eq!(combine_ticks(0xFFFF_FFFF, 0xFFFF_FFFF), u64::MAX);
+    }
+
+    #[test]
+    fn prescaler_ticks_per_second_matches_default_init() {
+        // `init` hardcodes prescaler 4, which is how the 1MHz default is
+        // derived from the 16MHz base clock.
+        assert_eq!(prescaler_ticks_per_second(4), 1_000_000);
+    }
+
+    #[test]
+    fn prescaler_ticks_per_second_covers_the_full_range() {
+        assert_eq!(prescaler_ticks_per_second(0), 16_000_000);
+        assert_eq!(prescaler_ticks_per_second(9), 16_000_000 >> 9);
+    }
+
+    #[test]
+    fn bitmode_max_value_matches_its_width() {
+        assert_eq!(Bitmode::B08.max_value(), 0xFF);
+        assert_eq!(Bitmode::B16.max_value(), 0xFFFF);
+        assert_eq!(Bitmode::B24.max_value(), 0x00FF_FFFF);
+        assert_eq!(Bitmode::B32.max_value(), 0xFFFF_FFFF);
+    }
+}